@@ -11,23 +11,53 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crossbeam_channel;
 use ::log::{log, warn};
+use serde::{Deserialize, Serialize};
 
 use crate::network::Network;
 
-// Message to send to the network message sender with the recipient and payload
-#[derive(Clone, Debug)]
+// The default interval at which the NetworkMessageSender wakes to check whether it has been
+// asked to shut down.
+const DEFAULT_SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// The most retry threads NetworkMessageSender will have in flight at once. Beyond this, a newly
+// failing send is routed straight to the dead letter (or dropped) instead of spawning another
+// thread, so a prolonged outage to one or more peers can't exhaust the process's threads.
+const MAX_CONCURRENT_RETRIES: usize = 64;
+
+// Who a SendRequest's payload should be delivered to: a single peer, an explicit list of peers,
+// or every peer the Network currently has connected.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Recipient {
+    One(String),
+    Many(Vec<String>),
+    AllConnected,
+}
+
+// Message to send to the network message sender with the recipient(s) and payload
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SendRequest {
-    recipient: String,
+    recipient: Recipient,
     payload: Vec<u8>,
 }
 
 impl SendRequest {
     pub fn new(recipient: String, payload: Vec<u8>) -> Self {
+        SendRequest::for_recipient(Recipient::One(recipient), payload)
+    }
+
+    pub fn for_recipient(recipient: Recipient, payload: Vec<u8>) -> Self {
         SendRequest { recipient, payload }
     }
 
-    pub fn recipient(&self) -> &str {
+    pub fn recipient(&self) -> &Recipient {
         &self.recipient
     }
 
@@ -36,28 +66,301 @@ impl SendRequest {
     }
 }
 
+// Configures how NetworkMessageSender reacts to a transient Network::send failure: how many
+// times to requeue the SendRequest, the base delay before the first retry (doubled after each
+// attempt), and where to route the message if every retry is exhausted.
+#[derive(Clone)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub backoff_base: Duration,
+    pub dead_letter: Option<Box<Sender<SendRequest>>>,
+}
+
+impl Default for RetryPolicy {
+    // No retries and no dead letter: a send failure is dropped immediately, matching the
+    // original warn!-and-drop behavior.
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 0,
+            backoff_base: Duration::from_millis(0),
+            dead_letter: None,
+        }
+    }
+}
+
+// Counters tracking send-path health: how many retry attempts have been made (one per attempt,
+// so a message retried twice counts twice), how many SendRequests have been successfully routed to the dead letter Sender after exhausting their retries, or
+// actually lost (retries exhausted, and either no dead letter was configured or that Sender
+// itself rejected the message). `dropped` and `dead_lettered` are mutually exclusive.
+#[derive(Default)]
+pub struct SendCounters {
+    dropped: AtomicUsize,
+    dead_lettered: AtomicUsize,
+    retried: AtomicUsize,
+}
+
+impl SendCounters {
+    pub fn dropped(&self) -> usize {
+        self.dropped.load(Ordering::SeqCst)
+    }
+
+    pub fn dead_lettered(&self) -> usize {
+        self.dead_lettered.load(Ordering::SeqCst)
+    }
+
+    pub fn retried(&self) -> usize {
+        self.retried.load(Ordering::SeqCst)
+    }
+}
+
 // The NetworkMessageSender recv messages that should be sent over the network. The Sender side of
 // the channel will be passed to handlers.
 pub struct NetworkMessageSender {
     rc: Box<Receiver<SendRequest>>,
     network: Network,
+    shutdown: Arc<AtomicBool>,
+    retry_policy: RetryPolicy,
+    counters: Arc<SendCounters>,
+    // JoinHandles for sends that failed and are being retried on their own thread, so `run` can
+    // wait for them to finish before returning on shutdown (otherwise a message that was already
+    // accepted could be lost mid-retry).
+    pending_retries: Arc<Mutex<Vec<thread::JoinHandle<()>>>>,
 }
 
 impl NetworkMessageSender {
     pub fn new(rc: Box<Receiver<SendRequest>>, network: Network) -> Self {
-        NetworkMessageSender { rc, network }
+        NetworkMessageSender::with_shutdown_signal(rc, network, Arc::new(AtomicBool::new(false)))
+    }
+
+    // Create a NetworkMessageSender that will stop its run loop and return once the given
+    // shutdown flag has been set to true, after draining any SendRequests still queued on the
+    // channel.
+    pub fn with_shutdown_signal(
+        rc: Box<Receiver<SendRequest>>,
+        network: Network,
+        shutdown: Arc<AtomicBool>,
+    ) -> Self {
+        NetworkMessageSender::with_retry_policy(rc, network, shutdown, RetryPolicy::default())
+    }
+
+    // Create a NetworkMessageSender that requeues a SendRequest up to `retry_policy.max_retries`
+    // times, with increasing delay, before routing it to `retry_policy.dead_letter` (if any) or
+    // dropping it.
+    pub fn with_retry_policy(
+        rc: Box<Receiver<SendRequest>>,
+        network: Network,
+        shutdown: Arc<AtomicBool>,
+        retry_policy: RetryPolicy,
+    ) -> Self {
+        NetworkMessageSender {
+            rc,
+            network,
+            shutdown,
+            retry_policy,
+            counters: Arc::new(SendCounters::default()),
+            pending_retries: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    // Counters tracking how many SendRequests have been dropped or dead-lettered and how many
+    // retry attempts have been made, so operators can observe send-path health.
+    pub fn counters(&self) -> Arc<SendCounters> {
+        self.counters.clone()
     }
 
     pub fn run(&self) -> Result<(), NetworkMessageSenderError> {
         loop {
-            let send_request = self.rc.recv()?;
-            match self
-                .network
-                .send(send_request.recipient().into(), send_request.payload())
-            {
-                Ok(_) => (),
-                Err(err) => warn!("Unable to send message: {:?}", err),
-            };
+            match self.rc.recv_timeout(DEFAULT_SHUTDOWN_POLL_INTERVAL) {
+                Ok(send_request) => self.send(send_request),
+                Err(RecvTimeoutError::Timeout) => {
+                    if self.shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+                // A single malformed frame (e.g. from an IpcReceiver that failed to deserialize
+                // the bytes it received) should not be treated the same as the channel having
+                // disconnected for good; log it and keep polling. A corrupted frame can arrive
+                // without waiting out the timeout, so nap for the poll interval before looping
+                // back around rather than busy-spinning on a persistently desynced channel.
+                Err(RecvTimeoutError::Corrupted) => {
+                    warn!("Dropping malformed SendRequest received on the channel");
+                    thread::sleep(DEFAULT_SHUTDOWN_POLL_INTERVAL);
+                }
+            }
+
+            if self.shutdown.load(Ordering::SeqCst) {
+                break;
+            }
+        }
+
+        self.drain_remaining();
+
+        // Wait for any in-flight retries to finish (and be dead-lettered or dropped) so that a
+        // SendRequest already accepted off the channel is never silently lost on shutdown.
+        for handle in self.pending_retries.lock().unwrap().drain(..) {
+            let _ = handle.join();
+        }
+
+        Ok(())
+    }
+
+    fn send(&self, send_request: SendRequest) {
+        // Share one buffer across every recipient instead of copying the payload per send.
+        let payload: Arc<[u8]> = Arc::from(send_request.payload());
+
+        for target in self.resolve_recipients(send_request.recipient()) {
+            // Attempt the first send synchronously, on the dispatch thread: only a failing send
+            // pays for a retry thread.
+            if let Err(err) = self.network.send(target.clone(), &payload) {
+                warn!(
+                    "Unable to send message to {}: {:?}; scheduling retry",
+                    target, err
+                );
+
+                let mut pending_retries = self.pending_retries.lock().unwrap();
+                // Drop finished retry threads so a long-running sender doesn't accumulate a
+                // JoinHandle per historical failure.
+                pending_retries.retain(|handle| !handle.is_finished());
+
+                if pending_retries.len() >= MAX_CONCURRENT_RETRIES {
+                    warn!(
+                        "Already retrying {} sends; routing message for {} directly to the \
+                         dead letter instead of spawning another retry",
+                        MAX_CONCURRENT_RETRIES, target
+                    );
+                    dead_letter_or_drop(&target, &payload, &self.retry_policy, &self.counters);
+                    continue;
+                }
+
+                let network = self.network.clone();
+                let payload = payload.clone();
+                let retry_policy = self.retry_policy.clone();
+                let counters = self.counters.clone();
+                let shutdown = self.shutdown.clone();
+
+                let handle = thread::spawn(move || {
+                    retry_until_exhausted(
+                        network,
+                        target,
+                        payload,
+                        retry_policy,
+                        counters,
+                        shutdown,
+                    );
+                });
+                pending_retries.push(handle);
+            }
+        }
+    }
+
+    fn resolve_recipients(&self, recipient: &Recipient) -> Vec<String> {
+        match recipient {
+            Recipient::One(peer_id) => vec![peer_id.clone()],
+            Recipient::Many(peer_ids) => peer_ids.clone(),
+            Recipient::AllConnected => self.network.peer_ids(),
+        }
+    }
+
+    // Drain any SendRequests that are still queued on the channel once shutdown has been
+    // requested, so messages that have already been accepted are not lost.
+    fn drain_remaining(&self) {
+        while let Ok(send_request) = self.rc.try_recv() {
+            self.send(send_request);
+        }
+    }
+}
+
+// Retries delivering `payload` to `target` over `network`, backing off between attempts, until
+// either a retry succeeds or `retry_policy.max_retries` attempts have failed. This runs entirely
+// on its own thread (spawned by `NetworkMessageSender::send` after the initial attempt failed),
+// looping internally rather than recursing into a new thread per attempt, so a slow or failing
+// recipient costs one thread for its whole retry lifetime rather than one per attempt. Once
+// retries are exhausted, the message is routed to `retry_policy.dead_letter` (if configured) or
+// dropped; these two outcomes are mutually exclusive, so `counters.dropped` only ever counts
+// messages that were actually lost. `shutdown` is checked between backoff naps so that `run`
+// joining this thread on shutdown doesn't block for a whole backoff schedule.
+fn retry_until_exhausted(
+    network: Network,
+    target: String,
+    payload: Arc<[u8]>,
+    retry_policy: RetryPolicy,
+    counters: Arc<SendCounters>,
+    shutdown: Arc<AtomicBool>,
+) {
+    // Number of retries already performed, not counting the initial attempt made by `send`
+    // before this function was ever spawned.
+    let mut attempt = 0u32;
+
+    loop {
+        if attempt >= retry_policy.max_retries {
+            dead_letter_or_drop(&target, &payload, &retry_policy, &counters);
+            return;
+        }
+
+        // Cap the exponent so a large max_retries can't overflow u32::pow or produce a
+        // nonsensical backoff duration.
+        let delay = retry_policy.backoff_base * 2u32.saturating_pow(attempt.min(31));
+        if sleep_or_shutdown(delay, &shutdown) {
+            dead_letter_or_drop(&target, &payload, &retry_policy, &counters);
+            return;
+        }
+
+        counters.retried.fetch_add(1, Ordering::SeqCst);
+        attempt += 1;
+
+        if network.send(target.clone(), &payload).is_ok() {
+            return;
+        }
+    }
+}
+
+// Sleeps for `delay`, in increments no longer than DEFAULT_SHUTDOWN_POLL_INTERVAL, returning
+// early (with `true`) as soon as `shutdown` is set rather than sleeping out the full delay.
+fn sleep_or_shutdown(delay: Duration, shutdown: &AtomicBool) -> bool {
+    let mut remaining = delay;
+
+    while remaining > Duration::from_millis(0) {
+        if shutdown.load(Ordering::SeqCst) {
+            return true;
+        }
+
+        let nap = remaining.min(DEFAULT_SHUTDOWN_POLL_INTERVAL);
+        thread::sleep(nap);
+        remaining -= nap;
+    }
+
+    shutdown.load(Ordering::SeqCst)
+}
+
+// Routes a SendRequest that has exhausted its retries (or that NetworkMessageSender declined to
+// retry because too many retries are already in flight) to `retry_policy.dead_letter`, if
+// configured, or else counts it as dropped. `dropped` and `dead_lettered` are mutually exclusive:
+// a message is only ever counted as dropped if it was not successfully handed to the dead letter.
+fn dead_letter_or_drop(
+    target: &str,
+    payload: &Arc<[u8]>,
+    retry_policy: &RetryPolicy,
+    counters: &SendCounters,
+) {
+    match retry_policy.dead_letter {
+        Some(ref dead_letter) => {
+            let send_request = SendRequest::new(target.to_string(), payload.to_vec());
+            // Count before sending so an observer that sees the message arrive on the dead
+            // letter channel is guaranteed to also see the counter updated.
+            counters.dead_lettered.fetch_add(1, Ordering::SeqCst);
+            if dead_letter.try_send(send_request).is_err() {
+                counters.dead_lettered.fetch_sub(1, Ordering::SeqCst);
+                counters.dropped.fetch_add(1, Ordering::SeqCst);
+                warn!(
+                    "Dead letter channel full or disconnected; dropping message for {}",
+                    target
+                );
+            }
+        }
+        None => {
+            counters.dropped.fetch_add(1, Ordering::SeqCst);
+            warn!("Unable to send message to {}: retries exhausted", target);
         }
     }
 }
@@ -73,12 +376,161 @@ impl From<RecvError> for NetworkMessageSenderError {
     }
 }
 
+// The discriminant that routes an inbound message to the Handler registered for it. It is
+// parsed from the first byte of the message payload; the remaining bytes are the handler's
+// payload.
+pub type MessageType = u8;
+
+// Implemented by anything that wants to react to a particular inbound message type. The reply
+// Sender is a clone of the Sender given to the owning NetworkMessageReceiver, so a handler can
+// push a SendRequest back out through the corresponding NetworkMessageSender.
+pub trait Handler: Send {
+    fn message_type(&self) -> MessageType;
+    fn handle(&self, peer_id: &str, payload: &[u8], reply: Box<Sender<SendRequest>>);
+}
+
+// Collects Handlers keyed by the MessageType they claim, then builds an immutable Dispatcher.
+#[derive(Default)]
+pub struct DispatchBuilder {
+    handlers: HashMap<MessageType, Box<Handler>>,
+}
+
+impl DispatchBuilder {
+    pub fn new() -> Self {
+        DispatchBuilder {
+            handlers: HashMap::new(),
+        }
+    }
+
+    pub fn with_handler(mut self, handler: Box<Handler>) -> Self {
+        self.handlers.insert(handler.message_type(), handler);
+        self
+    }
+
+    pub fn build(self) -> Dispatcher {
+        Dispatcher {
+            handlers: self.handlers,
+        }
+    }
+}
+
+// Routes an inbound payload to the Handler registered for its leading MessageType byte. Unknown
+// message types are logged and dropped rather than panicking, since a misbehaving or newer peer
+// should not be able to take down the receive loop.
+pub struct Dispatcher {
+    handlers: HashMap<MessageType, Box<Handler>>,
+}
+
+impl Dispatcher {
+    fn dispatch(&self, peer_id: &str, payload: &[u8], reply: Box<Sender<SendRequest>>) {
+        let message_type = match payload.first() {
+            Some(message_type) => *message_type,
+            None => {
+                warn!("Received empty payload from {}; dropping", peer_id);
+                return;
+            }
+        };
+
+        match self.handlers.get(&message_type) {
+            Some(handler) => handler.handle(peer_id, &payload[1..], reply),
+            None => warn!(
+                "No handler registered for message type {}; dropping message from {}",
+                message_type, peer_id
+            ),
+        }
+    }
+}
+
+// The mirror image of NetworkMessageSender: it owns a Network, polls network.recv_timeout() so it
+// can be shut down cleanly, and dispatches each inbound message to the Handler registered for it,
+// giving handlers a way to reply by way of the Sender they are handed.
+pub struct NetworkMessageReceiver {
+    network: Network,
+    dispatcher: Dispatcher,
+    reply_sender: Box<Sender<SendRequest>>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl NetworkMessageReceiver {
+    pub fn new(
+        network: Network,
+        dispatcher: Dispatcher,
+        reply_sender: Box<Sender<SendRequest>>,
+    ) -> Self {
+        NetworkMessageReceiver::with_shutdown_signal(
+            network,
+            dispatcher,
+            reply_sender,
+            Arc::new(AtomicBool::new(false)),
+        )
+    }
+
+    // Create a NetworkMessageReceiver that will stop its run loop and return once the given
+    // shutdown flag has been set to true, rather than blocking on network.recv() forever.
+    pub fn with_shutdown_signal(
+        network: Network,
+        dispatcher: Dispatcher,
+        reply_sender: Box<Sender<SendRequest>>,
+        shutdown: Arc<AtomicBool>,
+    ) -> Self {
+        NetworkMessageReceiver {
+            network,
+            dispatcher,
+            reply_sender,
+            shutdown,
+        }
+    }
+
+    pub fn run(&self) -> Result<(), NetworkMessageReceiverError> {
+        loop {
+            match self.network.recv_timeout(DEFAULT_SHUTDOWN_POLL_INTERVAL) {
+                Ok(network_message) => self.dispatcher.dispatch(
+                    network_message.peer_id(),
+                    network_message.payload(),
+                    self.reply_sender.clone(),
+                ),
+                Err(RecvTimeoutError::Timeout) => {
+                    if self.shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                }
+                // Unlike a timeout, a genuine disconnect is not something shutdown triggers on
+                // purpose, so it is surfaced as an error rather than treated like a clean exit.
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(NetworkMessageReceiverError::RecvError(
+                        "network disconnected".into(),
+                    ));
+                }
+                // A single malformed frame should not be treated the same as the network having
+                // disconnected for good; log it and keep polling, pacing with a poll-interval nap
+                // so a persistently desynced peer can't turn this into a busy spin.
+                Err(RecvTimeoutError::Corrupted) => {
+                    warn!("Dropping malformed network message");
+                    thread::sleep(DEFAULT_SHUTDOWN_POLL_INTERVAL);
+                }
+            }
+
+            if self.shutdown.load(Ordering::SeqCst) {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum NetworkMessageReceiverError {
+    RecvError(String),
+}
+
 // To allow the NetworkMessageSender to not make decissions about the threading model, any channel
 // that is used must have the following Receiver trait implemented, then the receiver end of the
 // channel can be passed to the NetworkMessageSender.
 pub trait Receiver<T>: Send {
     fn recv(&self) -> Result<T, RecvError>;
     fn try_recv(&self) -> Result<T, TryRecvError>;
+    fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError>;
 }
 
 // To allow the NetworkMessageSender to not make decissions about the threading model, any channel
@@ -86,6 +538,10 @@ pub trait Receiver<T>: Send {
 // can be passed to a Handler.
 pub trait Sender<T>: Send {
     fn send(&self, t: T) -> Result<(), SendError>;
+    // A non-blocking send: returns immediately with the rejected value, rather than blocking,
+    // when the channel is full or disconnected, so a producer on a bounded channel can retry or
+    // shed load instead of stalling.
+    fn try_send(&self, t: T) -> Result<(), TrySendError<T>>;
     fn box_clone(&self) -> Box<Sender<T>>;
 }
 
@@ -95,6 +551,49 @@ impl<T> Clone for Box<Sender<T>> {
     }
 }
 
+// Implement the Receiver and Sender traits generically for crossbeam channels, so a crossbeam
+// channel of any message type (not just SendRequest) can be used directly as a Sender/Receiver,
+// including as the byte channel underneath an IpcSender/IpcReceiver.
+impl<T: Send> Receiver<T> for crossbeam_channel::Receiver<T> {
+    fn recv(&self) -> Result<T, RecvError> {
+        crossbeam_channel::Receiver::recv(self).map_err(|err| RecvError {
+            error: err.to_string(),
+        })
+    }
+
+    fn try_recv(&self) -> Result<T, TryRecvError> {
+        crossbeam_channel::Receiver::try_recv(self).map_err(|err| TryRecvError {
+            error: err.to_string(),
+        })
+    }
+
+    fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        crossbeam_channel::Receiver::recv_timeout(self, timeout).map_err(|err| match err {
+            crossbeam_channel::RecvTimeoutError::Timeout => RecvTimeoutError::Timeout,
+            crossbeam_channel::RecvTimeoutError::Disconnected => RecvTimeoutError::Disconnected,
+        })
+    }
+}
+
+impl<T: Send> Sender<T> for crossbeam_channel::Sender<T> {
+    fn send(&self, t: T) -> Result<(), SendError> {
+        crossbeam_channel::Sender::send(self, t).map_err(|err| SendError {
+            error: err.to_string(),
+        })
+    }
+
+    fn try_send(&self, t: T) -> Result<(), TrySendError<T>> {
+        crossbeam_channel::Sender::try_send(self, t).map_err(|err| match err {
+            crossbeam_channel::TrySendError::Full(t) => TrySendError::Full(t),
+            crossbeam_channel::TrySendError::Disconnected(t) => TrySendError::Disconnected(t),
+        })
+    }
+
+    fn box_clone(&self) -> Box<Sender<T>> {
+        Box::new((*self).clone())
+    }
+}
+
 #[derive(Debug)]
 pub struct RecvError {
     error: String,
@@ -105,54 +604,214 @@ pub struct TryRecvError {
     error: String,
 }
 
+// Mirrors the semantics of crossbeam_channel::RecvTimeoutError and
+// std::sync::mpsc::RecvTimeoutError: a timeout is distinguished from the channel being
+// disconnected so callers can decide whether to keep polling. `Corrupted` is this module's own
+// addition, for channels (like IpcReceiver) that deserialize bytes into a SendRequest: it lets a
+// single malformed frame be logged and skipped rather than forcing the caller to treat it the
+// same as the channel having disconnected for good.
+#[derive(Debug, PartialEq)]
+pub enum RecvTimeoutError {
+    Timeout,
+    Disconnected,
+    Corrupted,
+}
+
 #[derive(Debug)]
 pub struct SendError {
     error: String,
 }
 
-#[cfg(test)]
-mod tests {
-    use crossbeam_channel;
+// Mirrors the semantics of crossbeam_channel::TrySendError/std::sync::mpsc::TrySendError: the
+// rejected value is returned so the caller can retry it or shed the load. `Corrupted` is this
+// module's own addition, for senders (like IpcSender) that serialize the value before handing it
+// to the underlying channel: it lets a value that failed to serialize be reported distinctly from
+// the channel itself being disconnected.
+#[derive(Debug)]
+pub enum TrySendError<T> {
+    Full(T),
+    Disconnected(T),
+    Corrupted(T),
+}
 
-    use std::sync::mpsc;
-    use std::thread;
+// Carries a SendRequest across a process boundary by serializing it and forwarding the bytes
+// over any channel that implements Sender<Vec<u8>>/Receiver<Vec<u8>>, e.g. a Unix socket or pipe
+// owned by a splitter/forwarder process.
+pub struct IpcSender {
+    inner: Box<Sender<Vec<u8>>>,
+}
 
-    use super::*;
-    use crate::mesh::Mesh;
-    use crate::network::Network;
-    use crate::transport::raw::RawTransport;
-    use crate::transport::Transport;
+impl IpcSender {
+    pub fn new(inner: Box<Sender<Vec<u8>>>) -> Self {
+        IpcSender { inner }
+    }
+}
 
-    // Implement the Receiver and Sender Traits for crossbeam channels
-    impl Receiver<SendRequest> for crossbeam_channel::Receiver<SendRequest> {
-        fn recv(&self) -> Result<SendRequest, RecvError> {
-            let request = crossbeam_channel::Receiver::recv(self).map_err(|err| RecvError {
-                error: err.to_string(),
-            })?;
-            Ok(request)
+impl Clone for IpcSender {
+    fn clone(&self) -> Self {
+        IpcSender {
+            inner: self.inner.clone(),
         }
+    }
+}
 
-        fn try_recv(&self) -> Result<SendRequest, TryRecvError> {
-            let request =
-                crossbeam_channel::Receiver::try_recv(self).map_err(|err| TryRecvError {
+impl Sender<SendRequest> for IpcSender {
+    fn send(&self, t: SendRequest) -> Result<(), SendError> {
+        let bytes = serde_json::to_vec(&t).map_err(|err| SendError {
+            error: err.to_string(),
+        })?;
+        self.inner.send(bytes)
+    }
+
+    fn try_send(&self, t: SendRequest) -> Result<(), TrySendError<SendRequest>> {
+        let bytes = match serde_json::to_vec(&t) {
+            Ok(bytes) => bytes,
+            Err(_) => return Err(TrySendError::Corrupted(t)),
+        };
+
+        self.inner.try_send(bytes).map_err(|err| match err {
+            TrySendError::Full(_) => TrySendError::Full(t),
+            TrySendError::Disconnected(_) => TrySendError::Disconnected(t),
+            TrySendError::Corrupted(_) => TrySendError::Corrupted(t),
+        })
+    }
+
+    fn box_clone(&self) -> Box<Sender<SendRequest>> {
+        Box::new(self.clone())
+    }
+}
+
+pub struct IpcReceiver {
+    inner: Box<Receiver<Vec<u8>>>,
+}
+
+impl IpcReceiver {
+    pub fn new(inner: Box<Receiver<Vec<u8>>>) -> Self {
+        IpcReceiver { inner }
+    }
+}
+
+impl Receiver<SendRequest> for IpcReceiver {
+    fn recv(&self) -> Result<SendRequest, RecvError> {
+        let bytes = self.inner.recv()?;
+        serde_json::from_slice(&bytes).map_err(|err| RecvError {
+            error: err.to_string(),
+        })
+    }
+
+    fn try_recv(&self) -> Result<SendRequest, TryRecvError> {
+        let bytes = self.inner.try_recv()?;
+        serde_json::from_slice(&bytes).map_err(|err| TryRecvError {
+            error: err.to_string(),
+        })
+    }
+
+    fn recv_timeout(&self, timeout: Duration) -> Result<SendRequest, RecvTimeoutError> {
+        let bytes = self.inner.recv_timeout(timeout)?;
+        serde_json::from_slice(&bytes).map_err(|_| RecvTimeoutError::Corrupted)
+    }
+}
+
+// Wraps either an in-process crossbeam channel or an IpcSender/IpcReceiver pair, selectable at
+// runtime. This lets a handler be pointed at whichever transport fits its deployment without
+// changing its code: the Channel variant moves the SendRequest with no copy, while Ipc crosses a
+// process boundary at the cost of a serialize/deserialize round trip.
+pub enum GenericSender {
+    Channel(crossbeam_channel::Sender<SendRequest>),
+    Ipc(IpcSender),
+}
+
+impl Clone for GenericSender {
+    fn clone(&self) -> Self {
+        match self {
+            GenericSender::Channel(sender) => GenericSender::Channel(sender.clone()),
+            GenericSender::Ipc(sender) => GenericSender::Ipc(sender.clone()),
+        }
+    }
+}
+
+impl Sender<SendRequest> for GenericSender {
+    fn send(&self, t: SendRequest) -> Result<(), SendError> {
+        match self {
+            GenericSender::Channel(sender) => {
+                crossbeam_channel::Sender::send(sender, t).map_err(|err| SendError {
                     error: err.to_string(),
-                })?;
-            Ok(request)
+                })
+            }
+            GenericSender::Ipc(sender) => sender.send(t),
         }
     }
 
-    impl Sender<SendRequest> for crossbeam_channel::Sender<SendRequest> {
-        fn send(&self, request: SendRequest) -> Result<(), SendError> {
-            crossbeam_channel::Sender::send(self, request).map_err(|err| SendError {
-                error: err.to_string(),
-            })?;
-            Ok(())
+    fn try_send(&self, t: SendRequest) -> Result<(), TrySendError<SendRequest>> {
+        match self {
+            GenericSender::Channel(sender) => crossbeam_channel::Sender::try_send(sender, t)
+                .map_err(|err| match err {
+                    crossbeam_channel::TrySendError::Full(t) => TrySendError::Full(t),
+                    crossbeam_channel::TrySendError::Disconnected(t) => {
+                        TrySendError::Disconnected(t)
+                    }
+                }),
+            GenericSender::Ipc(sender) => sender.try_send(t),
         }
+    }
 
-        fn box_clone(&self) -> Box<Sender<SendRequest>> {
-            Box::new((*self).clone())
+    fn box_clone(&self) -> Box<Sender<SendRequest>> {
+        Box::new(self.clone())
+    }
+}
+
+pub enum GenericReceiver {
+    Channel(crossbeam_channel::Receiver<SendRequest>),
+    Ipc(IpcReceiver),
+}
+
+impl Receiver<SendRequest> for GenericReceiver {
+    fn recv(&self) -> Result<SendRequest, RecvError> {
+        match self {
+            GenericReceiver::Channel(receiver) => crossbeam_channel::Receiver::recv(receiver)
+                .map_err(|err| RecvError {
+                    error: err.to_string(),
+                }),
+            GenericReceiver::Ipc(receiver) => receiver.recv(),
+        }
+    }
+
+    fn try_recv(&self) -> Result<SendRequest, TryRecvError> {
+        match self {
+            GenericReceiver::Channel(receiver) => crossbeam_channel::Receiver::try_recv(receiver)
+                .map_err(|err| TryRecvError {
+                    error: err.to_string(),
+                }),
+            GenericReceiver::Ipc(receiver) => receiver.try_recv(),
+        }
+    }
+
+    fn recv_timeout(&self, timeout: Duration) -> Result<SendRequest, RecvTimeoutError> {
+        match self {
+            GenericReceiver::Channel(receiver) => crossbeam_channel::Receiver::recv_timeout(
+                receiver, timeout,
+            )
+            .map_err(|err| match err {
+                crossbeam_channel::RecvTimeoutError::Timeout => RecvTimeoutError::Timeout,
+                crossbeam_channel::RecvTimeoutError::Disconnected => RecvTimeoutError::Disconnected,
+            }),
+            GenericReceiver::Ipc(receiver) => receiver.recv_timeout(timeout),
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossbeam_channel;
+
+    use std::sync::mpsc;
+    use std::thread;
+
+    use super::*;
+    use crate::mesh::Mesh;
+    use crate::network::Network;
+    use crate::transport::raw::RawTransport;
+    use crate::transport::Transport;
 
     // Implement the Receiver and Sender Traits for mpsc channels
     impl Receiver<SendRequest> for mpsc::Receiver<SendRequest> {
@@ -169,6 +828,13 @@ mod tests {
             })?;
             Ok(request)
         }
+
+        fn recv_timeout(&self, timeout: Duration) -> Result<SendRequest, RecvTimeoutError> {
+            mpsc::Receiver::recv_timeout(self, timeout).map_err(|err| match err {
+                mpsc::RecvTimeoutError::Timeout => RecvTimeoutError::Timeout,
+                mpsc::RecvTimeoutError::Disconnected => RecvTimeoutError::Disconnected,
+            })
+        }
     }
 
     impl Sender<SendRequest> for mpsc::Sender<SendRequest> {
@@ -179,6 +845,13 @@ mod tests {
             Ok(())
         }
 
+        // mpsc::Sender is unbounded, so it never reports Full; a disconnected receiver is the
+        // only way a send can be rejected.
+        fn try_send(&self, request: SendRequest) -> Result<(), TrySendError<SendRequest>> {
+            mpsc::Sender::send(self, request)
+                .map_err(|mpsc::SendError(request)| TrySendError::Disconnected(request))
+        }
+
         fn box_clone(&self) -> Box<Sender<SendRequest>> {
             Box::new((*self).clone())
         }
@@ -292,4 +965,299 @@ mod tests {
         test_network_message_sender_rapid_fire(Box::new(send), Box::new(recv));
     }
 
+    // Test that setting the shutdown flag causes a blocked run loop to return, rather than
+    // hanging forever waiting on a message that never arrives.
+    #[test]
+    fn test_shutdown_stops_run() {
+        let mesh = Mesh::new(1, 1);
+        let network = Network::new(mesh);
+
+        let (_send, recv): (
+            crossbeam_channel::Sender<SendRequest>,
+            crossbeam_channel::Receiver<SendRequest>,
+        ) = crossbeam_channel::bounded(5);
+
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let network_message_sender =
+            NetworkMessageSender::with_shutdown_signal(Box::new(recv), network, shutdown.clone());
+
+        let handle = thread::spawn(move || network_message_sender.run());
+
+        thread::sleep(Duration::from_millis(250));
+        shutdown.store(true, Ordering::SeqCst);
+
+        handle
+            .join()
+            .expect("run thread panicked")
+            .expect("run returned an error");
+    }
+
+    // Test that a GenericSender/GenericReceiver pair backed by the Channel variant passes a
+    // SendRequest through unchanged.
+    #[test]
+    fn test_generic_sender_channel() {
+        let (send, recv) = crossbeam_channel::bounded(5);
+        let sender = GenericSender::Channel(send);
+        let receiver = GenericReceiver::Channel(recv);
+
+        let send_request = SendRequest::new("123".to_string(), b"hello".to_vec());
+        Sender::send(&sender, send_request.clone()).unwrap();
+
+        let received = receiver.recv().unwrap();
+        assert_eq!(received.recipient(), send_request.recipient());
+        assert_eq!(received.payload(), send_request.payload());
+    }
+
+    // Test that a GenericSender/GenericReceiver pair backed by the Ipc variant round-trips a
+    // SendRequest through a serialize/deserialize step.
+    #[test]
+    fn test_generic_sender_ipc() {
+        let (byte_send, byte_recv): (
+            crossbeam_channel::Sender<Vec<u8>>,
+            crossbeam_channel::Receiver<Vec<u8>>,
+        ) = crossbeam_channel::bounded(5);
+
+        let sender = GenericSender::Ipc(IpcSender::new(Box::new(byte_send)));
+        let receiver = GenericReceiver::Ipc(IpcReceiver::new(Box::new(byte_recv)));
+
+        let send_request = SendRequest::new("123".to_string(), b"hello".to_vec());
+        Sender::send(&sender, send_request.clone()).unwrap();
+
+        let received = receiver.recv().unwrap();
+        assert_eq!(received.recipient(), send_request.recipient());
+        assert_eq!(received.payload(), send_request.payload());
+    }
+
+    // A Handler that echoes the payload it receives back to the peer that sent it, so tests can
+    // assert on what reached the reply Sender.
+    struct EchoHandler {
+        message_type: MessageType,
+    }
+
+    impl Handler for EchoHandler {
+        fn message_type(&self) -> MessageType {
+            self.message_type
+        }
+
+        fn handle(&self, peer_id: &str, payload: &[u8], reply: Box<Sender<SendRequest>>) {
+            reply
+                .send(SendRequest::new(peer_id.to_string(), payload.to_vec()))
+                .unwrap();
+        }
+    }
+
+    // Test that a payload whose first byte matches a registered Handler is routed to it with the
+    // MessageType byte stripped, and that the handler's reply reaches the outbound channel.
+    #[test]
+    fn test_dispatch_routes_to_registered_handler() {
+        let dispatcher = DispatchBuilder::new()
+            .with_handler(Box::new(EchoHandler { message_type: 1 }))
+            .build();
+
+        let (send, recv) = crossbeam_channel::bounded(1);
+        let reply: Box<Sender<SendRequest>> = Box::new(send);
+
+        dispatcher.dispatch("peer1", &[1, b'h', b'i'], reply);
+
+        let send_request = recv.recv().unwrap();
+        assert_eq!(
+            send_request.recipient(),
+            &Recipient::One("peer1".to_string())
+        );
+        assert_eq!(send_request.payload(), b"hi");
+    }
+
+    // Test that an unregistered message type is dropped rather than causing a panic, and that it
+    // does not produce a reply.
+    #[test]
+    fn test_dispatch_drops_unknown_message_type() {
+        let dispatcher = DispatchBuilder::new()
+            .with_handler(Box::new(EchoHandler { message_type: 1 }))
+            .build();
+
+        let (send, recv) = crossbeam_channel::bounded(1);
+        let reply: Box<Sender<SendRequest>> = Box::new(send);
+
+        dispatcher.dispatch("peer1", &[99, b'h', b'i'], reply);
+
+        assert!(recv.try_recv().is_err());
+    }
+
+    // Test that a Recipient::Many SendRequest is expanded into one network.send per listed peer,
+    // and that every peer receives the same payload.
+    #[test]
+    fn test_send_request_many_recipients() {
+        let mut transport = RawTransport::default();
+        let mut listener_a = transport.listen("127.0.0.1:0").unwrap();
+        let endpoint_a = listener_a.endpoint();
+        let mut listener_b = transport.listen("127.0.0.1:0").unwrap();
+        let endpoint_b = listener_b.endpoint();
+
+        let mesh1 = Mesh::new(2, 2);
+        let mut network1 = Network::new(mesh1.clone());
+
+        let (send, recv): (
+            crossbeam_channel::Sender<SendRequest>,
+            crossbeam_channel::Receiver<SendRequest>,
+        ) = crossbeam_channel::bounded(5);
+
+        let network_message_sender = NetworkMessageSender::new(Box::new(recv), network1.clone());
+
+        let handle_a = thread::spawn(move || {
+            let mesh_a = Mesh::new(1, 1);
+            let mut network_a = Network::new(mesh_a.clone());
+            let connection = listener_a.accept().unwrap();
+            network_a.add_peer("A".to_string(), connection).unwrap();
+            let network_message = network_a.recv().unwrap();
+            assert_eq!(network_message.payload().to_vec(), b"broadcast".to_vec());
+        });
+
+        let handle_b = thread::spawn(move || {
+            let mesh_b = Mesh::new(1, 1);
+            let mut network_b = Network::new(mesh_b.clone());
+            let connection = listener_b.accept().unwrap();
+            network_b.add_peer("B".to_string(), connection).unwrap();
+            let network_message = network_b.recv().unwrap();
+            assert_eq!(network_message.payload().to_vec(), b"broadcast".to_vec());
+        });
+
+        let connection_a = transport.connect(&endpoint_a).unwrap();
+        network1.add_peer("A".to_string(), connection_a).unwrap();
+        let connection_b = transport.connect(&endpoint_b).unwrap();
+        network1.add_peer("B".to_string(), connection_b).unwrap();
+
+        thread::spawn(move || network_message_sender.run());
+
+        let send_request = SendRequest::for_recipient(
+            Recipient::Many(vec!["A".to_string(), "B".to_string()]),
+            b"broadcast".to_vec(),
+        );
+        send.send(send_request).unwrap();
+
+        handle_a.join().unwrap();
+        handle_b.join().unwrap();
+    }
+
+    // Test that a send which never succeeds, because the target peer was never connected, is
+    // retried the configured number of times and then routed to the dead letter Sender, with the
+    // counters reflecting both.
+    #[test]
+    fn test_retry_then_dead_letter() {
+        let mesh = Mesh::new(1, 1);
+        let network = Network::new(mesh);
+
+        let (_send, recv): (
+            crossbeam_channel::Sender<SendRequest>,
+            crossbeam_channel::Receiver<SendRequest>,
+        ) = crossbeam_channel::bounded(5);
+
+        let (dead_letter_send, dead_letter_recv) = crossbeam_channel::bounded(5);
+        let dead_letter: Box<Sender<SendRequest>> = Box::new(dead_letter_send);
+
+        let retry_policy = RetryPolicy {
+            max_retries: 2,
+            backoff_base: Duration::from_millis(1),
+            dead_letter: Some(dead_letter),
+        };
+
+        let network_message_sender = NetworkMessageSender::with_retry_policy(
+            Box::new(recv),
+            network,
+            Arc::new(AtomicBool::new(false)),
+            retry_policy,
+        );
+
+        let send_request = SendRequest::new("nonexistent-peer".to_string(), b"hello".to_vec());
+        network_message_sender.send(send_request);
+
+        // Retries happen on their own thread (not the caller's) so the dispatch loop is never
+        // blocked; block on the dead letter channel here instead of sleeping an arbitrary amount.
+        let dead_lettered = dead_letter_recv
+            .recv_timeout(Duration::from_secs(5))
+            .expect("message was not dead-lettered in time");
+        assert_eq!(
+            dead_lettered.recipient(),
+            &Recipient::One("nonexistent-peer".to_string())
+        );
+        assert_eq!(dead_lettered.payload(), b"hello");
+
+        let counters = network_message_sender.counters();
+        assert_eq!(counters.retried(), 2);
+        assert_eq!(counters.dropped(), 0);
+        assert_eq!(counters.dead_lettered(), 1);
+    }
+
+    // Test that a real inbound network message, sent over an actual transport connection, is
+    // routed by NetworkMessageReceiver::run through the Dispatcher to the registered Handler, and
+    // that the handler's reply reaches the reply Sender.
+    #[test]
+    fn test_network_message_receiver_dispatches_to_handler() {
+        let mut transport = RawTransport::default();
+        let mut listener = transport.listen("127.0.0.1:0").unwrap();
+        let endpoint = listener.endpoint();
+
+        let mesh1 = Mesh::new(1, 1);
+        let mut network1 = Network::new(mesh1.clone());
+
+        let dispatcher = DispatchBuilder::new()
+            .with_handler(Box::new(EchoHandler { message_type: 1 }))
+            .build();
+
+        let (reply_send, reply_recv) = crossbeam_channel::bounded(1);
+        let reply_sender: Box<Sender<SendRequest>> = Box::new(reply_send);
+
+        let connection = transport.connect(&endpoint).unwrap();
+        network1.add_peer("ABC".to_string(), connection).unwrap();
+
+        let network_message_receiver =
+            NetworkMessageReceiver::new(network1.clone(), dispatcher, reply_sender);
+
+        thread::spawn(move || network_message_receiver.run());
+
+        let handle = thread::spawn(move || {
+            let mesh2 = Mesh::new(1, 1);
+            let mut network2 = Network::new(mesh2.clone());
+            let connection = listener.accept().unwrap();
+            network2.add_peer("123".to_string(), connection).unwrap();
+            network2.send("123".to_string(), &[1, b'h', b'i']).unwrap();
+        });
+
+        let send_request = reply_recv
+            .recv_timeout(Duration::from_secs(5))
+            .expect("handler reply did not arrive in time");
+        assert_eq!(send_request.recipient(), &Recipient::One("ABC".to_string()));
+        assert_eq!(send_request.payload(), b"hi");
+
+        handle.join().unwrap();
+    }
+
+    // Test that setting the shutdown flag causes a blocked NetworkMessageReceiver run loop to
+    // return, rather than hanging forever waiting on a network message that never arrives.
+    #[test]
+    fn test_shutdown_stops_receiver_run() {
+        let mesh = Mesh::new(1, 1);
+        let network = Network::new(mesh);
+
+        let dispatcher = DispatchBuilder::new().build();
+        let (reply_send, _reply_recv) = crossbeam_channel::bounded(1);
+        let reply_sender: Box<Sender<SendRequest>> = Box::new(reply_send);
+
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let network_message_receiver = NetworkMessageReceiver::with_shutdown_signal(
+            network,
+            dispatcher,
+            reply_sender,
+            shutdown.clone(),
+        );
+
+        let handle = thread::spawn(move || network_message_receiver.run());
+
+        thread::sleep(Duration::from_millis(250));
+        shutdown.store(true, Ordering::SeqCst);
+
+        handle
+            .join()
+            .expect("run thread panicked")
+            .expect("run returned an error");
+    }
 }